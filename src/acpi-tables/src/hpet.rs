@@ -0,0 +1,132 @@
+// Copyright 2024 Amazon.com, Inc. or its affiliates. All Rights Reserved.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::mem::size_of;
+
+use vm_memory::{Bytes, GuestAddress, GuestMemory};
+use zerocopy::little_endian::{U16, U32, U64};
+use zerocopy::AsBytes;
+
+use crate::{checksum, Result, Sdt, SdtHeader};
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+struct GenericAddressStructure {
+    address_space_id: u8,
+    register_bit_width: u8,
+    register_bit_offset: u8,
+    reserved: u8,
+    address: U64,
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Debug, AsBytes)]
+struct HpetHeader {
+    sdt: SdtHeader,
+    event_timer_block_id: U32,
+    base_address: GenericAddressStructure,
+    hpet_number: u8,
+    minimum_clock_tick: U16,
+    page_protection: u8,
+}
+
+/// High Precision Event Timer Description Table (HPET)
+///
+/// This table advertises the base address of the HPET's memory-mapped registers so the guest
+/// can discover it. More information about this table can be found in the IA-PC HPET
+/// specification:
+/// https://www.intel.com/content/dam/www/public/us/en/documents/technical-specifications/software-developers-hpet-spec-1-0a.pdf
+#[derive(Debug)]
+pub struct Hpet {
+    header: HpetHeader,
+}
+
+impl Hpet {
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        event_timer_block_id: u32,
+        base_address: u64,
+        hpet_number: u8,
+        minimum_clock_tick: u16,
+    ) -> Self {
+        let length = size_of::<HpetHeader>();
+        let sdt_header = SdtHeader::new(
+            *b"HPET",
+            length.try_into().unwrap(),
+            1,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+        );
+
+        let mut header = HpetHeader {
+            sdt: sdt_header,
+            event_timer_block_id: U32::new(event_timer_block_id),
+            base_address: GenericAddressStructure {
+                address_space_id: 0,
+                register_bit_width: 64,
+                register_bit_offset: 0,
+                reserved: 0,
+                address: U64::new(base_address),
+            },
+            hpet_number,
+            minimum_clock_tick: U16::new(minimum_clock_tick),
+            page_protection: 0,
+        };
+
+        header.sdt.checksum = checksum(&[header.as_bytes()]);
+
+        Hpet { header }
+    }
+}
+
+impl Sdt for Hpet {
+    fn len(&self) -> usize {
+        self.header.sdt.length.get().try_into().unwrap()
+    }
+
+    fn write_to_guest<M: GuestMemory>(&mut self, mem: &M, address: GuestAddress) -> Result<()> {
+        mem.write_slice(self.header.as_bytes(), address)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::little_endian::{U16, U32, U64};
+    use zerocopy::AsBytes;
+
+    use crate::hpet::Hpet;
+    use crate::Sdt;
+
+    #[test]
+    fn test_hpet() {
+        let hpet = Hpet::new(*b"FCAP  ", *b"FCHPET  ", 0, 0x8086_a201, 0xfed0_0000, 0, 1);
+        assert_eq!(hpet.len(), std::mem::size_of_val(&hpet.header));
+        assert_eq!(hpet.header.event_timer_block_id, U32::new(0x8086_a201));
+        assert_eq!(hpet.header.base_address.address_space_id, 0);
+        assert_eq!(hpet.header.base_address.register_bit_width, 64);
+        assert_eq!(hpet.header.base_address.address, U64::new(0xfed0_0000));
+        assert_eq!(hpet.header.hpet_number, 0);
+        assert_eq!(hpet.header.minimum_clock_tick, U16::new(1));
+
+        let sum = hpet
+            .header
+            .as_bytes()
+            .iter()
+            .fold(0u8, |acc, byte| acc.wrapping_add(*byte));
+        assert_eq!(sum, 0);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2024 Amazon.com, Inc. or its affiliates. All Rights Reserved.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal set of structures implementing the ACPI spec, required to boot a guest.
+
+use vm_memory::{GuestAddress, GuestMemory, GuestMemoryError};
+use zerocopy::little_endian::U32;
+use zerocopy::AsBytes;
+
+pub mod hpet;
+pub mod madt;
+pub mod mcfg;
+
+/// Errors thrown while building or writing ACPI tables.
+#[derive(Debug, thiserror::Error)]
+pub enum AcpiError {
+    /// Guest memory error: {0}
+    #[error("Guest memory error: {0}")]
+    GuestMemory(#[from] GuestMemoryError),
+    /// The requested guest address is invalid.
+    #[error("Invalid guest address")]
+    InvalidGuestAddress,
+}
+
+/// Specialized [`Result`](std::result::Result) type for ACPI table operations.
+pub type Result<T> = std::result::Result<T, AcpiError>;
+
+/// Trait implemented by all ACPI System Description Tables (SDTs).
+pub trait Sdt {
+    /// Returns the length in bytes of the serialized table.
+    fn len(&self) -> usize;
+
+    /// Serializes the table and writes it to guest memory at `address`.
+    fn write_to_guest<M: GuestMemory>(&mut self, mem: &M, address: GuestAddress) -> Result<()>;
+}
+
+/// Computes the ACPI checksum byte over `regions`, so that the sum of all bytes (including the
+/// checksum byte itself) is zero modulo 256.
+pub fn checksum(regions: &[&[u8]]) -> u8 {
+    let sum = regions
+        .iter()
+        .flat_map(|region| region.iter())
+        .fold(0u8, |acc, byte| acc.wrapping_add(*byte));
+
+    (255 - sum).wrapping_add(1)
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct SdtHeader {
+    pub(crate) signature: [u8; 4],
+    pub(crate) length: U32,
+    pub(crate) revision: u8,
+    pub(crate) checksum: u8,
+    pub(crate) oem_id: [u8; 6],
+    pub(crate) oem_table_id: [u8; 8],
+    pub(crate) oem_revision: U32,
+    pub(crate) creator_id: [u8; 4],
+    pub(crate) creator_revision: U32,
+}
+
+impl SdtHeader {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        signature: [u8; 4],
+        length: u32,
+        revision: u8,
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+    ) -> Self {
+        SdtHeader {
+            signature,
+            length: U32::new(length),
+            revision,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: U32::new(oem_revision),
+            creator_id: *b"FCAP",
+            creator_revision: U32::ZERO,
+        }
+    }
+}
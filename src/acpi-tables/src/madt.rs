@@ -6,7 +6,7 @@
 use std::mem::size_of;
 
 use vm_memory::{Address, Bytes, GuestAddress, GuestMemory};
-use zerocopy::little_endian::U32;
+use zerocopy::little_endian::{U16, U32, U64};
 use zerocopy::AsBytes;
 
 use crate::{checksum, AcpiError, Result, Sdt, SdtHeader};
@@ -14,6 +14,8 @@ use crate::{checksum, AcpiError, Result, Sdt, SdtHeader};
 const MADT_CPU_ENABLE_FLAG: u32 = 0;
 
 const MADT_CPU_ONLINE_CAPABLE_FLAG: u32 = 1;
+
+const MADT_GICC_ENABLED_FLAG: u32 = 0;
 // clippy doesn't understand that we actually "use" the fields of this struct when we serialize
 // them as bytes in guest memory, so here we just ignore dead code to avoid having to name
 // everything with an underscore prefix
@@ -50,6 +52,45 @@ impl LocalAPIC {
     }
 }
 
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct LocalX2Apic {
+    r#type: u8,
+    length: u8,
+    reserved: U16,
+    x2apic_id: U32,
+    flags: U32,
+    acpi_processor_uid: U32,
+}
+
+impl LocalX2Apic {
+    pub fn new(cpu_id: u32, online_capable: bool) -> Self {
+        if online_capable {
+            Self {
+                r#type: 9,
+                length: 16,
+                reserved: U16::ZERO,
+                x2apic_id: U32::new(cpu_id),
+                flags: U32::new(1u32 << MADT_CPU_ONLINE_CAPABLE_FLAG),
+                acpi_processor_uid: U32::new(cpu_id),
+            }
+        } else {
+            Self {
+                r#type: 9,
+                length: 16,
+                reserved: U16::ZERO,
+                x2apic_id: U32::new(cpu_id),
+                flags: U32::new(1u32 << MADT_CPU_ENABLE_FLAG),
+                acpi_processor_uid: U32::new(cpu_id),
+            }
+        }
+    }
+}
+
 // clippy doesn't understand that we actually "use" the fields of this struct when we serialize
 // them as bytes in guest memory, so here we just ignore dead code to avoid having to name
 // everything with an underscore prefix
@@ -78,6 +119,283 @@ impl IoAPIC {
     }
 }
 
+/// Polarity of an interrupt, encoded in bits 0-1 of the MPS INTI flags.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Polarity {
+    #[default]
+    Conforming,
+    ActiveHigh,
+    ActiveLow,
+}
+
+impl Polarity {
+    fn bits(self) -> u16 {
+        match self {
+            Polarity::Conforming => 0b00,
+            Polarity::ActiveHigh => 0b01,
+            Polarity::ActiveLow => 0b11,
+        }
+    }
+}
+
+/// Trigger mode of an interrupt, encoded in bits 2-3 of the MPS INTI flags.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TriggerMode {
+    #[default]
+    Conforming,
+    Edge,
+    Level,
+}
+
+impl TriggerMode {
+    fn bits(self) -> u16 {
+        match self {
+            TriggerMode::Conforming => 0b00,
+            TriggerMode::Edge => 0b01,
+            TriggerMode::Level => 0b11,
+        }
+    }
+}
+
+fn mps_inti_flags(polarity: Polarity, trigger_mode: TriggerMode) -> u16 {
+    polarity.bits() | (trigger_mode.bits() << 2)
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct InterruptSourceOverride {
+    r#type: u8,
+    length: u8,
+    bus: u8,
+    source: u8,
+    global_system_interrupt: U32,
+    flags: U16,
+}
+
+impl InterruptSourceOverride {
+    pub fn new(
+        bus: u8,
+        source: u8,
+        global_system_interrupt: u32,
+        polarity: Polarity,
+        trigger_mode: TriggerMode,
+    ) -> Self {
+        InterruptSourceOverride {
+            r#type: 2,
+            length: 10,
+            bus,
+            source,
+            global_system_interrupt: U32::new(global_system_interrupt),
+            flags: U16::new(mps_inti_flags(polarity, trigger_mode)),
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct NmiSource {
+    r#type: u8,
+    length: u8,
+    flags: U16,
+    global_system_interrupt: U32,
+}
+
+impl NmiSource {
+    pub fn new(
+        global_system_interrupt: u32,
+        polarity: Polarity,
+        trigger_mode: TriggerMode,
+    ) -> Self {
+        NmiSource {
+            r#type: 3,
+            length: 8,
+            flags: U16::new(mps_inti_flags(polarity, trigger_mode)),
+            global_system_interrupt: U32::new(global_system_interrupt),
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct LocalApicNmi {
+    r#type: u8,
+    length: u8,
+    processor_uid: u8,
+    flags: U16,
+    local_apic_lint: u8,
+}
+
+impl LocalApicNmi {
+    pub fn new(
+        processor_uid: u8,
+        local_apic_lint: u8,
+        polarity: Polarity,
+        trigger_mode: TriggerMode,
+    ) -> Self {
+        LocalApicNmi {
+            r#type: 4,
+            length: 6,
+            processor_uid,
+            flags: U16::new(mps_inti_flags(polarity, trigger_mode)),
+            local_apic_lint,
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct GICC {
+    r#type: u8,
+    length: u8,
+    reserved: U16,
+    cpu_interface_number: U32,
+    acpi_processor_uid: U32,
+    flags: U32,
+    parking_protocol_version: U32,
+    performance_interrupt_gsiv: U32,
+    parked_address: U64,
+    physical_base_address: U64,
+    gicv: U64,
+    gich: U64,
+    vgic_maintenance_interrupt: U32,
+    gicr_base_address: U64,
+    mpidr: U64,
+    processor_power_efficiency_class: u8,
+    reserved2: [u8; 3],
+}
+
+impl GICC {
+    pub fn new(
+        cpu_interface_number: u32,
+        acpi_processor_uid: u32,
+        physical_base_address: u64,
+        gicr_base_address: u64,
+        mpidr: u64,
+    ) -> Self {
+        GICC {
+            r#type: 0x0B,
+            length: 80,
+            reserved: U16::ZERO,
+            cpu_interface_number: U32::new(cpu_interface_number),
+            acpi_processor_uid: U32::new(acpi_processor_uid),
+            flags: U32::new(1u32 << MADT_GICC_ENABLED_FLAG),
+            parking_protocol_version: U32::ZERO,
+            performance_interrupt_gsiv: U32::ZERO,
+            parked_address: U64::ZERO,
+            physical_base_address: U64::new(physical_base_address),
+            gicv: U64::ZERO,
+            gich: U64::ZERO,
+            vgic_maintenance_interrupt: U32::ZERO,
+            gicr_base_address: U64::new(gicr_base_address),
+            mpidr: U64::new(mpidr),
+            processor_power_efficiency_class: 0,
+            reserved2: [0; 3],
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct GICD {
+    r#type: u8,
+    length: u8,
+    reserved: U16,
+    gic_id: U32,
+    physical_base_address: U64,
+    system_vector_base: U32,
+    gic_version: u8,
+    reserved2: [u8; 3],
+}
+
+impl GICD {
+    pub fn new(gic_id: u32, physical_base_address: u64, gic_version: u8) -> Self {
+        GICD {
+            r#type: 0x0C,
+            length: 24,
+            reserved: U16::ZERO,
+            gic_id: U32::new(gic_id),
+            physical_base_address: U64::new(physical_base_address),
+            system_vector_base: U32::ZERO,
+            gic_version,
+            reserved2: [0; 3],
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct GICR {
+    r#type: u8,
+    length: u8,
+    reserved: U16,
+    discovery_range_base_address: U64,
+    discovery_range_length: U32,
+}
+
+impl GICR {
+    pub fn new(discovery_range_base_address: u64, discovery_range_length: u32) -> Self {
+        GICR {
+            r#type: 0x0E,
+            length: 16,
+            reserved: U16::ZERO,
+            discovery_range_base_address: U64::new(discovery_range_base_address),
+            discovery_range_length: U32::new(discovery_range_length),
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct ITS {
+    r#type: u8,
+    length: u8,
+    reserved: U16,
+    gic_its_id: U32,
+    physical_base_address: U64,
+    reserved2: U32,
+}
+
+impl ITS {
+    pub fn new(gic_its_id: u32, physical_base_address: u64) -> Self {
+        ITS {
+            r#type: 0x0F,
+            length: 20,
+            reserved: U16::ZERO,
+            gic_its_id: U32::new(gic_its_id),
+            physical_base_address: U64::new(physical_base_address),
+            reserved2: U32::ZERO,
+        }
+    }
+}
+
 // clippy doesn't understand that we actually "use" the fields of this struct when we serialize
 // them as bytes in guest memory, so here we just ignore dead code to avoid having to name
 // everything with an underscore prefix
@@ -154,9 +472,12 @@ impl Sdt for Madt {
 
 #[cfg(test)]
 mod tests {
-    use zerocopy::little_endian::U32;
+    use zerocopy::little_endian::{U16, U32};
 
-    use crate::madt::LocalAPIC;
+    use crate::madt::{
+        InterruptSourceOverride, LocalAPIC, LocalApicNmi, LocalX2Apic, NmiSource, Polarity,
+        TriggerMode, GICC, GICD, GICR, ITS,
+    };
 
     #[test]
     fn test_online_local_apic() {
@@ -177,4 +498,84 @@ mod tests {
         assert_eq!(online_capable_apic.apic_id, 1);
         assert_eq!(online_capable_apic.flags, U32::new(2));
     }
+
+    #[test]
+    fn test_online_x2apic() {
+        let online_x2apic = LocalX2Apic::new(300, false);
+        assert_eq!(online_x2apic.r#type, 9);
+        assert_eq!(online_x2apic.length, 16);
+        assert_eq!(online_x2apic.x2apic_id, U32::new(300));
+        assert_eq!(online_x2apic.acpi_processor_uid, U32::new(300));
+        assert_eq!(online_x2apic.flags, U32::new(1));
+    }
+
+    #[test]
+    fn test_online_capable_x2apic() {
+        let online_capable_x2apic = LocalX2Apic::new(301, true);
+        assert_eq!(online_capable_x2apic.r#type, 9);
+        assert_eq!(online_capable_x2apic.length, 16);
+        assert_eq!(online_capable_x2apic.x2apic_id, U32::new(301));
+        assert_eq!(online_capable_x2apic.acpi_processor_uid, U32::new(301));
+        assert_eq!(online_capable_x2apic.flags, U32::new(2));
+    }
+
+    #[test]
+    fn test_interrupt_source_override() {
+        // The typical ISA IRQ0 -> GSI2 timer remap.
+        let iso =
+            InterruptSourceOverride::new(0, 0, 2, Polarity::Conforming, TriggerMode::Conforming);
+        assert_eq!(iso.r#type, 2);
+        assert_eq!(iso.length, 10);
+        assert_eq!(iso.global_system_interrupt, U32::new(2));
+        assert_eq!(iso.flags, U16::new(0));
+    }
+
+    #[test]
+    fn test_nmi_source() {
+        let nmi = NmiSource::new(1, Polarity::ActiveHigh, TriggerMode::Edge);
+        assert_eq!(nmi.r#type, 3);
+        assert_eq!(nmi.length, 8);
+        assert_eq!(nmi.global_system_interrupt, U32::new(1));
+        assert_eq!(nmi.flags, U16::new(0b0101));
+    }
+
+    #[test]
+    fn test_local_apic_nmi() {
+        let nmi = LocalApicNmi::new(0xff, 1, Polarity::ActiveLow, TriggerMode::Level);
+        assert_eq!(nmi.r#type, 4);
+        assert_eq!(nmi.length, 6);
+        assert_eq!(nmi.processor_uid, 0xff);
+        assert_eq!(nmi.local_apic_lint, 1);
+        assert_eq!(nmi.flags, U16::new(0b1111));
+    }
+
+    #[test]
+    fn test_gicc() {
+        let gicc = GICC::new(0, 0, 0x2c00_0000, 0x2c01_0000, 0);
+        assert_eq!(gicc.r#type, 0x0B);
+        assert_eq!(gicc.length, 80);
+        assert_eq!(gicc.flags, U32::new(1));
+    }
+
+    #[test]
+    fn test_gicd() {
+        let gicd = GICD::new(0, 0x2c00_1000, 3);
+        assert_eq!(gicd.r#type, 0x0C);
+        assert_eq!(gicd.length, 24);
+        assert_eq!(gicd.gic_version, 3);
+    }
+
+    #[test]
+    fn test_gicr() {
+        let gicr = GICR::new(0x2c01_0000, 0x0002_0000);
+        assert_eq!(gicr.r#type, 0x0E);
+        assert_eq!(gicr.length, 16);
+    }
+
+    #[test]
+    fn test_its() {
+        let its = ITS::new(0, 0x2c20_0000);
+        assert_eq!(its.r#type, 0x0F);
+        assert_eq!(its.length, 20);
+    }
 }
@@ -0,0 +1,127 @@
+// Copyright 2024 Amazon.com, Inc. or its affiliates. All Rights Reserved.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::mem::size_of;
+
+use vm_memory::{Address, Bytes, GuestAddress, GuestMemory};
+use zerocopy::little_endian::{U16, U64};
+use zerocopy::AsBytes;
+
+use crate::{checksum, AcpiError, Result, Sdt, SdtHeader};
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Default, AsBytes)]
+pub struct McfgAllocation {
+    base_address: U64,
+    pci_segment_group_number: U16,
+    start_bus_number: u8,
+    end_bus_number: u8,
+    reserved: [u8; 4],
+}
+
+impl McfgAllocation {
+    pub fn new(
+        base_address: u64,
+        pci_segment_group_number: u16,
+        start_bus_number: u8,
+        end_bus_number: u8,
+    ) -> Self {
+        McfgAllocation {
+            base_address: U64::new(base_address),
+            pci_segment_group_number: U16::new(pci_segment_group_number),
+            start_bus_number,
+            end_bus_number,
+            reserved: [0; 4],
+        }
+    }
+}
+
+// clippy doesn't understand that we actually "use" the fields of this struct when we serialize
+// them as bytes in guest memory, so here we just ignore dead code to avoid having to name
+// everything with an underscore prefix
+#[allow(dead_code)]
+#[repr(packed)]
+#[derive(Debug, AsBytes)]
+struct McfgHeader {
+    sdt: SdtHeader,
+    reserved: [u8; 8],
+}
+
+/// PCI Express memory mapped configuration space base address Description Table (MCFG)
+///
+/// This table advertises the base addresses corresponding to the non-empty PCI Express bus
+/// segments enabled on a guest. More information about this table can be found in the PCI
+/// Firmware Specification:
+/// https://members.pcisig.com/wg/PCI-SIG/document/8023
+#[derive(Debug)]
+pub struct Mcfg {
+    header: McfgHeader,
+    allocations: Vec<u8>,
+}
+
+impl Mcfg {
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        allocations: Vec<u8>,
+    ) -> Self {
+        let length = size_of::<McfgHeader>() + allocations.len();
+        let sdt_header = SdtHeader::new(
+            *b"MCFG",
+            length.try_into().unwrap(),
+            1,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+        );
+
+        let mut header = McfgHeader {
+            sdt: sdt_header,
+            reserved: [0; 8],
+        };
+
+        header.sdt.checksum = checksum(&[header.as_bytes(), allocations.as_bytes()]);
+
+        Mcfg {
+            header,
+            allocations,
+        }
+    }
+}
+
+impl Sdt for Mcfg {
+    fn len(&self) -> usize {
+        self.header.sdt.length.get().try_into().unwrap()
+    }
+
+    fn write_to_guest<M: GuestMemory>(&mut self, mem: &M, address: GuestAddress) -> Result<()> {
+        mem.write_slice(self.header.as_bytes(), address)?;
+        let address = address
+            .checked_add(size_of::<McfgHeader>() as u64)
+            .ok_or(AcpiError::InvalidGuestAddress)?;
+        mem.write_slice(self.allocations.as_bytes(), address)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::little_endian::U16;
+
+    use crate::mcfg::McfgAllocation;
+
+    #[test]
+    fn test_mcfg_allocation() {
+        let allocation = McfgAllocation::new(0xb000_0000, 0, 0, 255);
+        assert_eq!(allocation.pci_segment_group_number, U16::new(0));
+        assert_eq!(allocation.start_bus_number, 0);
+        assert_eq!(allocation.end_bus_number, 255);
+    }
+}